@@ -0,0 +1,695 @@
+use std::convert::TryFrom;
+use once_cell::sync::Lazy;
+use regex::Regex;
+#[cfg(feature = "serialization")]
+use serde::{Deserialize, Serialize};
+
+static REGEX_STEAMID2: Lazy<Regex> = Lazy::new(|| Regex::new(r"^STEAM_([0-5]):([01]):(\d+$)").unwrap());
+static REGEX_STEAMID3: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\[(.):([01]):(\d+)\]$").unwrap());
+static REGEX_INVITECODE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^[bcdfghjkmnpqrtvw]{1,5}(-[bcdfghjkmnpqrtvw]{1,3})?$").unwrap()
+});
+static REGEX_CSGO_FRIEND_CODE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^[ABCDEFGHJKLMNPQRSTUVWXYZ23456789]{4}-[ABCDEFGHJKLMNPQRSTUVWXYZ23456789]{4}$").unwrap()
+});
+
+const HEX_ALPHABET: &str = "0123456789abcdef";
+const INVITE_CODE_ALPHABET: &str = "bcdfghjkmnpqrtvw";
+const CSGO_FRIEND_CODE_ALPHABET: &str = "ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+/// The base URL Steam "friend invite" links are served from, e.g. `https://s.team/p/cv-dgb`.
+pub const INVITE_CODE_URL_BASE: &str = "https://s.team/p/";
+
+/* Valve SteamID Format:
+ *  A SteamID is just a packed 64-bit unsigned integer!
+ *
+ * It consists of five parts, from least to most significant bit:
+ *  1. Authentication Server    - 1 bit     (1)
+ *  2. Account Number           - 31 bits   (32)
+ *  3. Instance                 - 20 bits   (52)
+ *  4. Account Type             - 4 bits    (56)
+ *  5. Universe                 - 8 bits    (64)
+ *
+ * This can be visualized like so:
+ *  1. _______________________________________________________________X
+ *  2. ________________________________XXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX_
+ *  3. ____________XXXXXXXXXXXXXXXXXXXX________________________________
+ *  4. ________XXXX____________________________________________________
+ *  5. XXXXXXXX________________________________________________________
+ *
+ * There are multiple ways to express a SteamID, some are lossy.
+ *  A. steamID64        - (1)+(2)+(3)+(4)+(5)
+ *  B. steamID2         - STEAM_(5):(1):(2)
+ *  C. steamID3         - [(4):(5):(1)+(2)]
+*/
+
+/// Errors produced when parsing a SteamID: a raw integer that does not correspond to a known
+/// SteamID component, a numeric field too wide for its slot, or input in no known format.
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    /// The universe byte did not correspond to a known `Universe` value.
+    BadUniverse(u8),
+    /// The account type nibble did not correspond to a known `AccountType` value.
+    BadAccountType(u8),
+    /// The instance bits did not correspond to a known `Instance` value.
+    BadInstance(u32),
+    /// A numeric field parsed successfully but didn't fit the number of bits Valve allots it,
+    /// e.g. a SteamID2 account number wider than 31 bits.
+    Overflow,
+    /// The input didn't match any known SteamID representation.
+    Malformed,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::BadUniverse(v) => write!(f, "invalid universe: {}", v),
+            ParseError::BadAccountType(v) => write!(f, "invalid account type: {}", v),
+            ParseError::BadInstance(v) => write!(f, "invalid instance: {}", v),
+            ParseError::Overflow => write!(f, "numeric field too large for its SteamID slot"),
+            ParseError::Malformed => write!(f, "unable to parse to any SteamID format"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The "universe" a SteamID belongs to, occupying the top 8 bits of a steamID64.
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Universe {
+    Invalid,
+    Public,
+    Beta,
+    Internal,
+    Dev,
+    ReleaseCandidate,
+}
+
+impl Universe {
+    pub fn to_u8(self) -> u8 {
+        match self {
+            Universe::Invalid => 0,
+            Universe::Public => 1,
+            Universe::Beta => 2,
+            Universe::Internal => 3,
+            Universe::Dev => 4,
+            Universe::ReleaseCandidate => 5,
+        }
+    }
+}
+
+impl TryFrom<u8> for Universe {
+    type Error = ParseError;
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Universe::Invalid),
+            1 => Ok(Universe::Public),
+            2 => Ok(Universe::Beta),
+            3 => Ok(Universe::Internal),
+            4 => Ok(Universe::Dev),
+            5 => Ok(Universe::ReleaseCandidate),
+            other => Err(ParseError::BadUniverse(other)),
+        }
+    }
+}
+
+/// The account type nibble of a SteamID, occupying bits 52-55 of a steamID64.
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountType {
+    Invalid,
+    Individual,
+    Multiseat,
+    GameServer,
+    AnonGameServer,
+    Pending,
+    ContentServer,
+    Clan,
+    Chat,
+    /// Formerly "P2PSuperSeeder" in Valve's SDK; this account type slot was later repurposed.
+    ConsoleUser,
+    AnonUser,
+}
+
+impl AccountType {
+    pub fn to_u8(self) -> u8 {
+        match self {
+            AccountType::Invalid => 0,
+            AccountType::Individual => 1,
+            AccountType::Multiseat => 2,
+            AccountType::GameServer => 3,
+            AccountType::AnonGameServer => 4,
+            AccountType::Pending => 5,
+            AccountType::ContentServer => 6,
+            AccountType::Clan => 7,
+            AccountType::Chat => 8,
+            AccountType::ConsoleUser => 9,
+            AccountType::AnonUser => 10,
+        }
+    }
+
+    /// The steamID3 letter for this account type. For `AccountType::Chat`, prefer
+    /// `chat_instance_flags_to_char`, which distinguishes clan chat (`c`) from lobby (`L`) and
+    /// matchmaking lobby (`T`) chats.
+    fn to_char(self) -> char {
+        match self {
+            AccountType::Invalid => 'I',
+            AccountType::Individual => 'U',
+            AccountType::Multiseat => 'M',
+            AccountType::GameServer => 'G',
+            AccountType::AnonGameServer => 'A',
+            AccountType::Pending => 'P',
+            AccountType::ContentServer => 'C',
+            AccountType::Clan => 'g',
+            AccountType::Chat => 'c',
+            AccountType::ConsoleUser => 'I',
+            AccountType::AnonUser => 'a',
+        }
+    }
+
+    fn from_char(account_type: char) -> AccountType {
+        match account_type {
+            'I' => AccountType::Invalid,
+            'U' => AccountType::Individual,
+            'M' => AccountType::Multiseat,
+            'G' => AccountType::GameServer,
+            'A' => AccountType::AnonGameServer,
+            'P' => AccountType::Pending,
+            'C' => AccountType::ContentServer,
+            'g' => AccountType::Clan,
+            'c' => AccountType::Chat,
+            'T' => AccountType::Chat,
+            'L' => AccountType::Chat,
+            'a' => AccountType::AnonUser,
+            _ => AccountType::Invalid,
+        }
+    }
+}
+
+impl TryFrom<u8> for AccountType {
+    type Error = ParseError;
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(AccountType::Invalid),
+            1 => Ok(AccountType::Individual),
+            2 => Ok(AccountType::Multiseat),
+            3 => Ok(AccountType::GameServer),
+            4 => Ok(AccountType::AnonGameServer),
+            5 => Ok(AccountType::Pending),
+            6 => Ok(AccountType::ContentServer),
+            7 => Ok(AccountType::Clan),
+            8 => Ok(AccountType::Chat),
+            9 => Ok(AccountType::ConsoleUser),
+            10 => Ok(AccountType::AnonUser),
+            other => Err(ParseError::BadAccountType(other)),
+        }
+    }
+}
+
+/// The instance of a SteamID, occupying bits 32-51 of a steamID64.
+///
+/// For chat-type accounts the same bits instead hold `EChatSteamIDInstanceFlags`; see
+/// `SteamID::set_chat_instance_flags`.
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instance {
+    All,
+    Desktop,
+    Console,
+    Web,
+}
+
+impl Instance {
+    pub fn to_u32(self) -> u32 {
+        match self {
+            Instance::All => 0,
+            Instance::Desktop => 1,
+            Instance::Console => 2,
+            Instance::Web => 4,
+        }
+    }
+}
+
+impl TryFrom<u32> for Instance {
+    type Error = ParseError;
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Instance::All),
+            1 => Ok(Instance::Desktop),
+            2 => Ok(Instance::Console),
+            4 => Ok(Instance::Web),
+            other => Err(ParseError::BadInstance(other)),
+        }
+    }
+}
+
+/// `EChatSteamIDInstanceFlags`, from Valve's `steamclientpublic.h`. For `AccountType::Chat`
+/// SteamIDs, the top 8 bits of the 20-bit instance field hold these flags instead of an
+/// `Instance` value.
+pub const CHAT_INSTANCE_FLAG_CLAN: u8 = 0x80;
+pub const CHAT_INSTANCE_FLAG_LOBBY: u8 = 0x40;
+pub const CHAT_INSTANCE_FLAG_MMSLOBBY: u8 = 0x20;
+
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq)]
+pub enum SteamIDFormat {
+    SteamID64,
+    SteamID2,
+    SteamID3,
+    InviteCode,
+    CsgoFriendCode,
+}
+
+/// A parsed, strongly-typed SteamID.
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SteamID {
+    account_id: u32,
+    instance: Instance,
+    /// The top 8 bits of the 20-bit instance field, meaningful only for `AccountType::Chat`.
+    chat_instance_flags: u8,
+    account_type: AccountType,
+    universe: Universe,
+}
+
+impl SteamID {
+    pub fn new() -> SteamID {
+        SteamID {
+            account_id: 0,
+            instance: Instance::Desktop,
+            chat_instance_flags: 0,
+            account_type: AccountType::Individual,
+            universe: Universe::Public,
+        }
+    }
+
+    pub fn account_id(&self) -> u32 {
+        self.account_id
+    }
+
+    pub fn instance(&self) -> Instance {
+        self.instance
+    }
+
+    pub fn account_type(&self) -> AccountType {
+        self.account_type
+    }
+
+    pub fn universe(&self) -> Universe {
+        self.universe
+    }
+
+    /// The raw `EChatSteamIDInstanceFlags` byte; only meaningful when `account_type()` is
+    /// `AccountType::Chat`. See `CHAT_INSTANCE_FLAG_CLAN`/`_LOBBY`/`_MMSLOBBY`.
+    pub fn chat_instance_flags(&self) -> u8 {
+        self.chat_instance_flags
+    }
+
+    pub fn set_account_id(&mut self, account_id: u32) {
+        self.account_id = account_id;
+    }
+
+    pub fn set_instance(&mut self, instance: Instance) {
+        self.instance = instance;
+    }
+
+    pub fn set_account_type(&mut self, account_type: AccountType) {
+        self.account_type = account_type;
+    }
+
+    pub fn set_universe(&mut self, universe: Universe) {
+        self.universe = universe;
+    }
+
+    pub fn set_chat_instance_flags(&mut self, flags: u8) {
+        self.chat_instance_flags = flags;
+    }
+
+    /// Unpacks a steamID64 into its typed components, rejecting any field that doesn't
+    /// correspond to a known `Universe`, `AccountType`, or `Instance`.
+    pub fn set_steamid64(&mut self, steamid_64: u64) -> Result<(), ParseError> {
+        let account_id = (steamid_64 & 0xFFFF_FFFF) as u32;
+        let raw_instance = u32::try_from(steamid_64 >> 32 & 0xFFFFF).unwrap();
+        let instance = Instance::try_from(raw_instance & 0xFFF)?;
+        let chat_instance_flags = (raw_instance >> 12) as u8;
+        let account_type = AccountType::try_from(u8::try_from(steamid_64 >> 52 & 0xF).unwrap())?;
+        let universe = Universe::try_from(u8::try_from(steamid_64 >> 56).unwrap())?;
+
+        self.account_id = account_id;
+        self.instance = instance;
+        self.chat_instance_flags = chat_instance_flags;
+        self.account_type = account_type;
+        self.universe = universe;
+        Ok(())
+    }
+
+    pub fn get_steamid64(&self) -> u64 {
+        let instance = u64::from(self.instance.to_u32()) | (u64::from(self.chat_instance_flags) << 12);
+        u64::from(self.account_id)
+            | instance << 32
+            | u64::from(self.account_type.to_u8()) << 52
+            | u64::from(self.universe.to_u8()) << 56
+    }
+
+    pub fn get_steamid2(&self) -> String {
+        let authserver: u32 = self.account_id & 1; // Ideally we'd cast this to a bool and convert that to a 0 or 1 later.
+        let accountid: u32 = (self.account_id >> 1) & 2147483647;
+        format!("STEAM_{}:{}:{}", self.universe.to_u8(), authserver, accountid)
+    }
+
+    pub fn get_steamid3(&self) -> String {
+        let type_char = if self.account_type == AccountType::Chat {
+            chat_instance_flags_to_char(self.chat_instance_flags)
+        } else {
+            self.account_type.to_char()
+        };
+        format!("[{}:{}:{}]", type_char, self.universe.to_u8(), self.account_id)
+    }
+
+    /// Encodes this account id as a Steam "friend invite" code, e.g. `cv-dgb`, as seen in
+    /// `https://s.team/p/<code>` links.
+    pub fn to_invite_code(&self) -> String {
+        let hex = format!("{:x}", self.account_id);
+        let mapped: String = hex
+            .chars()
+            .map(|c| {
+                let index = HEX_ALPHABET.find(c).expect("{:x} only emits hex digits");
+                INVITE_CODE_ALPHABET.as_bytes()[index] as char
+            })
+            .collect();
+        if mapped.len() > 3 {
+            let split = mapped.len() - 3;
+            format!("{}-{}", &mapped[..split], &mapped[split..])
+        } else {
+            mapped
+        }
+    }
+
+    /// Encodes this account id as a full `https://s.team/p/<code>` invite URL.
+    pub fn to_invite_url(&self) -> String {
+        format!("{}{}", INVITE_CODE_URL_BASE, self.to_invite_code())
+    }
+
+    /// Encodes this account id as a CS:GO/CS2 friend code, e.g. `CTK2-RTMA`.
+    pub fn to_csgo_friend_code(&self) -> String {
+        let account_id = self.account_id;
+        let hash = csgo_friend_code_hash(account_id);
+
+        // Interleave the account id's nibbles with the hash's bits (ValvePython's scheme):
+        // 8 nibbles (32 bits) plus one hash bit apiece is exactly 40 bits, i.e. 8 base32
+        // digits with nothing left over to pad out.
+        let mut result: u64 = 0;
+        for i in 0..8u32 {
+            let nibble = u64::from((account_id >> (4 * i)) & 0xF);
+            let bit = u64::from((hash >> i) & 1);
+            result = (result << 4) | nibble;
+            result = (result << 1) | bit;
+        }
+
+        let alphabet = CSGO_FRIEND_CODE_ALPHABET.as_bytes();
+        let mut encoded = [b'A'; 8];
+        let mut value = result;
+        for slot in encoded.iter_mut().rev() {
+            *slot = alphabet[(value & 0x1F) as usize];
+            value >>= 5;
+        }
+
+        let body = std::str::from_utf8(&encoded).unwrap();
+        format!("{}-{}", &body[0..4], &body[4..8])
+    }
+}
+
+/// The steamID3 letter for a `AccountType::Chat` SteamID's `EChatSteamIDInstanceFlags` byte:
+/// `c` for clan chat, `L` for lobby, `T` for matchmaking lobby (defaulting to clan chat).
+fn chat_instance_flags_to_char(flags: u8) -> char {
+    if flags & CHAT_INSTANCE_FLAG_CLAN != 0 {
+        'c'
+    } else if flags & CHAT_INSTANCE_FLAG_LOBBY != 0 {
+        'L'
+    } else if flags & CHAT_INSTANCE_FLAG_MMSLOBBY != 0 {
+        'T'
+    } else {
+        'c'
+    }
+}
+
+/// Hashes an account id the way CS:GO/CS2 friend codes do: pack it into an 8-byte little-endian
+/// buffer tagged with the ASCII bytes "CSGO", MD5 it, and read the first 4 bytes big-endian.
+fn csgo_friend_code_hash(account_id: u32) -> u32 {
+    let tagged = u64::from(account_id) | 0x4353474F00000000;
+    let digest = md5::compute(tagged.to_le_bytes());
+    u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]])
+}
+
+impl Default for SteamID {
+    fn default() -> Self {
+        SteamID::new()
+    }
+}
+
+pub fn string_to_steamid_type(steamid: &str) -> Result<SteamIDFormat, ParseError> {
+    if steamid.parse::<u64>().is_ok() {
+        return Ok(SteamIDFormat::SteamID64);
+    }
+    if REGEX_STEAMID2.is_match(steamid) {
+        return Ok(SteamIDFormat::SteamID2);
+    }
+    if REGEX_STEAMID3.is_match(steamid) {
+        return Ok(SteamIDFormat::SteamID3);
+    }
+    if REGEX_INVITECODE.is_match(steamid) {
+        return Ok(SteamIDFormat::InviteCode);
+    }
+    if REGEX_CSGO_FRIEND_CODE.is_match(steamid) {
+        return Ok(SteamIDFormat::CsgoFriendCode);
+    }
+    Err(ParseError::Malformed)
+}
+
+/// Parses a SteamID2 string (`STEAM_1:0:11101`), following Valve's `DecimalToUint64` discipline:
+/// every numeric field is range-checked rather than allowed to silently overflow.
+pub fn steamid2_to_steamid64(steamid2: &str) -> Result<u64, ParseError> {
+    let captures = REGEX_STEAMID2.captures(steamid2).ok_or(ParseError::Malformed)?;
+
+    // The universe digit and auth server bit are already constrained by the regex ([0-5], [01]).
+    let universe = captures[1].parse::<u64>().unwrap();
+    let auth_server = captures[2].parse::<u64>().unwrap();
+
+    // The account number is 31 bits; anything wider can't round-trip through a steamID64.
+    let account_id = captures[3].parse::<u64>().map_err(|_| ParseError::Overflow)?;
+    if account_id > 0x7FFF_FFFF {
+        return Err(ParseError::Overflow);
+    }
+
+    Ok((universe << 56) | auth_server | (account_id << 1) | 76561197960265728)
+}
+
+/// Parses a SteamID3 string (`[U:1:22202]`). The `T`/`L`/`c` prefixes used for chat instances
+/// set the corresponding `EChatSteamIDInstanceFlags` bit rather than just `AccountType::Chat`.
+pub fn steamid3_to_steamid64(steamid3: &str) -> Result<u64, ParseError> {
+    let captures = REGEX_STEAMID3.captures(steamid3).ok_or(ParseError::Malformed)?;
+
+    let account_type_char = captures[1].parse::<char>().map_err(|_| ParseError::Malformed)?;
+    let account_type_value = AccountType::from_char(account_type_char);
+    let account_type = u64::from(account_type_value.to_u8());
+    let chat_instance_flags: u64 = match account_type_char {
+        'c' => u64::from(CHAT_INSTANCE_FLAG_CLAN),
+        'L' => u64::from(CHAT_INSTANCE_FLAG_LOBBY),
+        'T' => u64::from(CHAT_INSTANCE_FLAG_MMSLOBBY),
+        _ => 0,
+    };
+    // Chat SteamIDs use the instance field's top byte for flags instead; everything else gets
+    // the conventional Desktop instance.
+    let instance: u64 = if account_type_value == AccountType::Chat {
+        0
+    } else {
+        u64::from(Instance::Desktop.to_u32())
+    };
+
+    // Already constrained by the regex ([01]).
+    let universe = captures[2].parse::<u64>().unwrap();
+
+    let account_id = captures[3].parse::<u64>().map_err(|_| ParseError::Overflow)?;
+    if account_id > u64::from(u32::MAX) {
+        return Err(ParseError::Overflow);
+    }
+
+    Ok((account_type << 52)
+        | (universe << 56)
+        | (chat_instance_flags << 44)
+        | (instance << 32)
+        | account_id)
+}
+
+/// Decodes a Steam "friend invite" code (e.g. `cv-dgb`) back into a steamID64, assuming the
+/// usual Individual/Public/Desktop account.
+pub fn invite_code_to_steamid64(invite_code: &str) -> Result<u64, ParseError> {
+    let mut hex = String::with_capacity(invite_code.len());
+    for c in invite_code.chars() {
+        if c == '-' {
+            continue;
+        }
+        let index = INVITE_CODE_ALPHABET.find(c).ok_or(ParseError::Malformed)?;
+        hex.push(HEX_ALPHABET.as_bytes()[index] as char);
+    }
+    let account_id = u32::from_str_radix(&hex, 16).map_err(|_| ParseError::Overflow)?;
+
+    let mut steamid = SteamID::new();
+    steamid.set_account_id(account_id);
+    steamid.set_universe(Universe::Public);
+    steamid.set_account_type(AccountType::Individual);
+    steamid.set_instance(Instance::Desktop);
+    Ok(steamid.get_steamid64())
+}
+
+/// Decodes a CS:GO/CS2 friend code (e.g. `CTK2-RTMA`) back into a steamID64, assuming the
+/// usual Individual/Public/Desktop account.
+pub fn csgo_friend_code_to_steamid64(friend_code: &str) -> Result<u64, ParseError> {
+    let mut value: u64 = 0;
+    for c in friend_code.chars() {
+        if c == '-' {
+            continue;
+        }
+        let index = CSGO_FRIEND_CODE_ALPHABET.find(c).ok_or(ParseError::Malformed)?;
+        value = (value << 5) | index as u64;
+    }
+
+    // Undo the nibble/bit interleaving, starting from the last pair that was pushed in.
+    let mut account_id: u32 = 0;
+    for i in (0..8u32).rev() {
+        let nibble = ((value >> 1) & 0xF) as u32;
+        value >>= 5;
+        account_id |= nibble << (4 * i);
+    }
+
+    let mut steamid = SteamID::new();
+    steamid.set_account_id(account_id);
+    steamid.set_universe(Universe::Public);
+    steamid.set_account_type(AccountType::Individual);
+    steamid.set_instance(Instance::Desktop);
+    Ok(steamid.get_steamid64())
+}
+
+pub fn string_to_steamid64(input: &str) -> Result<u64, ParseError> {
+    match string_to_steamid_type(input)? {
+        SteamIDFormat::SteamID64 => Ok(input.parse::<u64>().unwrap()),
+        SteamIDFormat::SteamID2 => steamid2_to_steamid64(input),
+        SteamIDFormat::SteamID3 => steamid3_to_steamid64(input),
+        SteamIDFormat::InviteCode => invite_code_to_steamid64(input),
+        SteamIDFormat::CsgoFriendCode => csgo_friend_code_to_steamid64(input),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn universe_try_from_rejects_out_of_range_values() {
+        assert_eq!(Universe::try_from(6), Err(ParseError::BadUniverse(6)));
+    }
+
+    #[test]
+    fn account_type_try_from_rejects_out_of_range_values() {
+        assert_eq!(AccountType::try_from(11), Err(ParseError::BadAccountType(11)));
+    }
+
+    #[test]
+    fn instance_try_from_rejects_out_of_range_values() {
+        assert_eq!(Instance::try_from(3), Err(ParseError::BadInstance(3)));
+    }
+
+    #[test]
+    fn invite_code_round_trips_through_an_account_id() {
+        let mut steamid = SteamID::new();
+        steamid.set_account_id(123456);
+        steamid.set_universe(Universe::Public);
+        steamid.set_account_type(AccountType::Individual);
+        steamid.set_instance(Instance::Desktop);
+
+        assert_eq!(steamid.to_invite_code(), "cv-dgb");
+        assert_eq!(invite_code_to_steamid64("cv-dgb").unwrap(), steamid.get_steamid64());
+    }
+
+    #[test]
+    fn csgo_friend_code_round_trips_through_an_account_id() {
+        let mut steamid = SteamID::new();
+        steamid.set_account_id(123456);
+        steamid.set_universe(Universe::Public);
+        steamid.set_account_type(AccountType::Individual);
+        steamid.set_instance(Instance::Desktop);
+
+        let friend_code = steamid.to_csgo_friend_code();
+        assert_eq!(friend_code, "AJE6-CABA");
+        assert_eq!(csgo_friend_code_to_steamid64(&friend_code).unwrap(), steamid.get_steamid64());
+    }
+
+    #[test]
+    fn individual_steamid3_gets_the_desktop_instance() {
+        let steamid64 = steamid3_to_steamid64("[U:1:22202]").unwrap();
+
+        let mut steamid = SteamID::new();
+        steamid.set_steamid64(steamid64).unwrap();
+        assert_eq!(steamid.instance(), Instance::Desktop);
+        assert_eq!(steamid.get_steamid3(), "[U:1:22202]");
+        assert_eq!(steamid.get_steamid64(), steamid64);
+    }
+
+    #[test]
+    fn clan_chat_instance_flags_round_trip_through_steamid3() {
+        let steamid64 = steamid3_to_steamid64("[c:1:4567]").unwrap();
+
+        let mut steamid = SteamID::new();
+        steamid.set_steamid64(steamid64).unwrap();
+        assert_eq!(steamid.chat_instance_flags(), CHAT_INSTANCE_FLAG_CLAN);
+        assert_eq!(steamid.get_steamid3(), "[c:1:4567]");
+        assert_eq!(steamid.get_steamid64(), steamid64);
+    }
+
+    #[test]
+    fn steamid2_account_number_wider_than_31_bits_overflows() {
+        assert_eq!(steamid2_to_steamid64("STEAM_1:0:2147483648"), Err(ParseError::Overflow));
+    }
+
+    #[test]
+    fn steamid2_account_number_too_long_to_parse_overflows() {
+        assert_eq!(
+            steamid2_to_steamid64("STEAM_1:0:99999999999999999999"),
+            Err(ParseError::Overflow)
+        );
+    }
+
+    #[test]
+    fn steamid3_account_number_wider_than_32_bits_overflows() {
+        assert_eq!(steamid3_to_steamid64("[U:1:4294967296]"), Err(ParseError::Overflow));
+    }
+
+    #[test]
+    fn garbage_input_is_malformed() {
+        assert_eq!(string_to_steamid_type("not-a-steamid"), Err(ParseError::Malformed));
+        assert_eq!(steamid2_to_steamid64("not-a-steamid"), Err(ParseError::Malformed));
+    }
+
+    #[test]
+    fn set_steamid64_rejects_bad_universe() {
+        let steamid64 = (1u64 << 32) | (1u64 << 52) | (6u64 << 56);
+        assert_eq!(SteamID::new().set_steamid64(steamid64), Err(ParseError::BadUniverse(6)));
+    }
+
+    #[test]
+    fn set_steamid64_rejects_bad_account_type() {
+        let steamid64 = (1u64 << 32) | (11u64 << 52) | (1u64 << 56);
+        assert_eq!(
+            SteamID::new().set_steamid64(steamid64),
+            Err(ParseError::BadAccountType(11))
+        );
+    }
+
+    #[test]
+    fn set_steamid64_rejects_bad_instance() {
+        let steamid64 = (3u64 << 32) | (1u64 << 52) | (1u64 << 56);
+        assert_eq!(SteamID::new().set_steamid64(steamid64), Err(ParseError::BadInstance(3)));
+    }
+}